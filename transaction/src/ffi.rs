@@ -2,10 +2,66 @@
 use crate::{TransactionBuilder, ObjectInput, Function, Argument};
 use crate::builder::ResolvedArgument;
 use sui_sdk_types::{Address, TypeTag, Identifier};
+use std::cell::RefCell;
+use std::ffi::CString;
 use std::slice;
 use std::mem;
 use std::str::FromStr;
 
+// ── Last-error channel ───────────────────────────────────────────────────────
+//
+// FFI entry points collapse failures into sentinel return values (`-1`, `-2`,
+// NULL, …), which tells a Go caller *that* something failed but not *why*.
+// Every fallible function below stashes a human-readable message here before
+// returning its sentinel, and clears it on success. `last_error_len` /
+// `last_error_message` let Go pull the message out after seeing a failure.
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+fn set_last_error(message: impl std::fmt::Display) {
+    let message = CString::new(message.to_string())
+        .unwrap_or_else(|_| CString::new("error message contained a NUL byte").unwrap());
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+}
+
+/// Length, in bytes, of the current thread's last-error message (excluding
+/// the NUL terminator). Returns 0 if no error is recorded.
+#[no_mangle]
+pub extern "C" fn last_error_len() -> usize {
+    LAST_ERROR.with(|slot| {
+        slot.borrow().as_ref().map(|s| s.as_bytes().len()).unwrap_or(0)
+    })
+}
+
+/// Copy the current thread's last-error message into the buffer at `ptr`,
+/// which must have at least `len` bytes of capacity.
+///
+/// Returns the number of bytes written on success, or -1 if there is no
+/// recorded error or `len` is too small for it.
+#[no_mangle]
+pub unsafe extern "C" fn last_error_message(ptr: *mut u8, len: usize) -> i32 {
+    LAST_ERROR.with(|slot| {
+        let slot = slot.borrow();
+        let message = match slot.as_ref() {
+            Some(m) => m,
+            None => return -1,
+        };
+        let bytes = message.as_bytes();
+        if bytes.len() > len {
+            return -1;
+        }
+        let out = slice::from_raw_parts_mut(ptr, bytes.len());
+        out.copy_from_slice(bytes);
+        bytes.len() as i32
+    })
+}
+
 // ── Memory Management ────────────────────────────────────────────────────────
 
 /// Allocate `len` bytes of WASM-linear memory for Go to write into.
@@ -48,10 +104,19 @@ struct ConfigParams {
     sender: Address,
     gas_budget: Option<u64>,
     gas_price: Option<u64>,
+    gas_owner: Option<Address>,
 }
 
-/// Set sender, gas_budget, and gas_price from a JSON object.
-/// JSON shape: `{"sender":"0x…","gas_budget":10000000,"gas_price":1000}`
+/// Set sender, gas_budget, gas_price, and (optionally) gas_owner from a JSON
+/// object.
+///
+/// JSON shape: `{"sender":"0x…","gas_budget":10000000,"gas_price":1000,"gas_owner":"0x…"}`
+///
+/// `gas_owner` is only needed for sponsored transactions, where a party other
+/// than `sender` supplies and owns the gas coins (e.g. a gas station paying
+/// fees for a user-authored PTB). When omitted, the gas owner defaults to
+/// `sender` as usual.
+///
 /// Returns 1 on success, -1 on parse error.
 #[no_mangle]
 pub unsafe extern "C" fn set_config(
@@ -66,9 +131,14 @@ pub unsafe extern "C" fn set_config(
             builder.set_sender(p.sender);
             if let Some(b) = p.gas_budget { builder.set_gas_budget(b); }
             if let Some(p) = p.gas_price  { builder.set_gas_price(p);  }
+            if let Some(o) = p.gas_owner  { builder.set_gas_owner(o);  }
+            clear_last_error();
             1
         }
-        Err(_) => -1,
+        Err(e) => {
+            set_last_error(e);
+            -1
+        }
     }
 }
 
@@ -96,12 +166,19 @@ pub unsafe extern "C" fn add_gas_object(
         Ok(g) => {
             let digest = match sui_sdk_types::Digest::from_str(&g.digest) {
                 Ok(d)  => d,
-                Err(_) => return -2,
+                Err(e) => {
+                    set_last_error(e);
+                    return -2;
+                }
             };
             builder.add_gas_objects(vec![ObjectInput::owned(g.id, g.version, digest)]);
+            clear_last_error();
             1
         }
-        Err(_) => -1,
+        Err(e) => {
+            set_last_error(e);
+            -1
+        }
     }
 }
 
@@ -147,18 +224,27 @@ pub unsafe extern "C" fn input_object(
     let bytes = slice::from_raw_parts(json_ptr, json_len);
     let p: ObjectInputParams = match serde_json::from_slice(bytes) {
         Ok(v)  => v,
-        Err(_) => return -1,
+        Err(e) => {
+            set_last_error(e);
+            return -1;
+        }
     };
 
     let obj = match p.kind.as_str() {
         "owned" | "immutable" | "receiving" => {
             let digest_str = match &p.digest {
                 Some(d) => d,
-                None    => return -2,
+                None    => {
+                    set_last_error(format!("missing \"digest\" for kind \"{}\"", p.kind));
+                    return -2;
+                }
             };
             let digest = match sui_sdk_types::Digest::from_str(digest_str) {
                 Ok(d)  => d,
-                Err(_) => return -2,
+                Err(e) => {
+                    set_last_error(e);
+                    return -2;
+                }
             };
             match p.kind.as_str() {
                 "owned"     => ObjectInput::owned(p.id, p.version, digest),
@@ -167,9 +253,13 @@ pub unsafe extern "C" fn input_object(
             }
         }
         "shared" => ObjectInput::shared(p.id, p.version, p.mutable.unwrap_or(true)),
-        _        => return -3,
+        kind => {
+            set_last_error(format!("unknown object input kind \"{}\"", kind));
+            return -3;
+        }
     };
 
+    clear_last_error();
     builder.object(obj).id as i64
 }
 
@@ -229,11 +319,20 @@ pub unsafe extern "C" fn pure_address(
     let bytes = slice::from_raw_parts(ptr, len);
     let s = match std::str::from_utf8(bytes) {
         Ok(s)  => s,
-        Err(_) => return -1,
+        Err(e) => {
+            set_last_error(e);
+            return -1;
+        }
     };
     match Address::from_str(s.trim().trim_matches('"')) {
-        Ok(addr) => (&mut *builder).pure(&addr).id as i64,
-        Err(_)   => -1,
+        Ok(addr) => {
+            clear_last_error();
+            (&mut *builder).pure(&addr).id as i64
+        }
+        Err(e) => {
+            set_last_error(e);
+            -1
+        }
     }
 }
 
@@ -324,15 +423,24 @@ pub unsafe extern "C" fn command_move_call(
     let bytes = slice::from_raw_parts(json_ptr, json_len);
     let req: Req = match serde_json::from_slice(bytes) {
         Ok(r)  => r,
-        Err(_) => return -1,
+        Err(e) => {
+            set_last_error(e);
+            return -1;
+        }
     };
     let module = match Identifier::from_str(&req.module) {
         Ok(m)  => m,
-        Err(_) => return -2,
+        Err(e) => {
+            set_last_error(e);
+            return -2;
+        }
     };
     let function = match Identifier::from_str(&req.function) {
         Ok(f)  => f,
-        Err(_) => return -3,
+        Err(e) => {
+            set_last_error(e);
+            return -3;
+        }
     };
     let mut args = Vec::new();
     for a in req.arguments {
@@ -341,6 +449,7 @@ pub unsafe extern "C" fn command_move_call(
             CallArg::PureBcs { pure_bcs } => args.push(builder.pure_bytes(pure_bcs)),
         }
     }
+    clear_last_error();
     builder.move_call(
         Function::new(req.package, module, function).with_type_args(req.type_args),
         args,
@@ -367,11 +476,15 @@ pub unsafe extern "C" fn command_split_coins(
     amount_arg_ids_ptr: *const u64,
     count: usize,
 ) -> i64 {
-    if count == 0 { return -1; }
+    if count == 0 {
+        set_last_error("command_split_coins: count must be >= 1");
+        return -1;
+    }
     let builder = &mut *builder;
     let coin    = Argument::new(coin_arg_id as usize);
     let amounts = slice::from_raw_parts(amount_arg_ids_ptr, count)
         .iter().map(|&id| Argument::new(id as usize)).collect();
+    clear_last_error();
     builder.split_coins(coin, amounts)[0].id as i64
 }
 
@@ -389,12 +502,16 @@ pub unsafe extern "C" fn command_merge_coins(
     source_arg_ids_ptr: *const u64,
     count: usize,
 ) -> i32 {
-    if count == 0 { return -1; }
+    if count == 0 {
+        set_last_error("command_merge_coins: count must be >= 1");
+        return -1;
+    }
     let builder = &mut *builder;
     let target  = Argument::new(target_coin_arg_id as usize);
     let sources = slice::from_raw_parts(source_arg_ids_ptr, count)
         .iter().map(|&id| Argument::new(id as usize)).collect();
     builder.merge_coins(target, sources);
+    clear_last_error();
     1
 }
 
@@ -412,12 +529,16 @@ pub unsafe extern "C" fn command_transfer_objects(
     count: usize,
     recipient_arg_id: u64,
 ) -> i32 {
-    if count == 0 { return -1; }
+    if count == 0 {
+        set_last_error("command_transfer_objects: count must be >= 1");
+        return -1;
+    }
     let builder  = &mut *builder;
     let objects  = slice::from_raw_parts(object_arg_ids_ptr, count)
         .iter().map(|&id| Argument::new(id as usize)).collect();
     let recipient = Argument::new(recipient_arg_id as usize);
     builder.transfer_objects(objects, recipient);
+    clear_last_error();
     1
 }
 
@@ -447,11 +568,17 @@ pub unsafe extern "C" fn command_make_move_vec(
         let bytes = slice::from_raw_parts(type_tag_ptr, type_tag_len);
         let s = match std::str::from_utf8(bytes) {
             Ok(s)  => s,
-            Err(_) => return -1,
+            Err(e) => {
+                set_last_error(e);
+                return -1;
+            }
         };
         match s.trim().parse::<TypeTag>() {
             Ok(t)  => Some(t),
-            Err(_) => return -2,
+            Err(e) => {
+                set_last_error(e);
+                return -2;
+            }
         }
     };
 
@@ -462,6 +589,7 @@ pub unsafe extern "C" fn command_make_move_vec(
             .iter().map(|&id| Argument::new(id as usize)).collect()
     };
 
+    clear_last_error();
     builder.make_move_vec(type_tag, elements).id as i64
 }
 
@@ -490,8 +618,12 @@ pub unsafe extern "C" fn command_publish(
     let bytes = slice::from_raw_parts(json_ptr, json_len);
     let req: Req = match serde_json::from_slice(bytes) {
         Ok(r)  => r,
-        Err(_) => return -1,
+        Err(e) => {
+            set_last_error(e);
+            return -1;
+        }
     };
+    clear_last_error();
     builder.publish(req.modules, req.dependencies).id as i64
 }
 
@@ -527,8 +659,12 @@ pub unsafe extern "C" fn command_upgrade(
     let bytes = slice::from_raw_parts(json_ptr, json_len);
     let req: Req = match serde_json::from_slice(bytes) {
         Ok(r)  => r,
-        Err(_) => return -1,
+        Err(e) => {
+            set_last_error(e);
+            return -1;
+        }
     };
+    clear_last_error();
     builder.upgrade(
         req.modules,
         req.dependencies,
@@ -558,8 +694,157 @@ pub unsafe extern "C" fn build_transaction(builder: *mut TransactionBuilder) ->
         bcs::to_bytes(&tx).map_err(|e| crate::error::Error::Input(e.to_string()))
     }) {
         Ok(b)  => b,
-        Err(_) => return std::ptr::null_mut(),
+        Err(e) => {
+            set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+    clear_last_error();
+    let total = 4 + payload.len();
+    let mut buf = Vec::<u8>::with_capacity(total);
+    buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&payload);
+    debug_assert_eq!(buf.len(), total);
+    debug_assert_eq!(buf.capacity(), total);
+    let ptr = buf.as_mut_ptr();
+    mem::forget(buf);
+    ptr
+}
+
+/// Serialise the fully-built transaction to BCS and append its digest.
+///
+/// Returns a pointer to a heap buffer laid out as:
+///   `[u32 bcs_len (LE 4 bytes)][BCS bytes … bcs_len bytes][32-byte digest]`
+///
+/// The digest is the `TransactionDigest` Sui itself assigns to the
+/// transaction — `Blake2b256("TransactionData::" ++ bcs(TransactionData))` —
+/// computed by `sui_sdk_types::TransactionData::digest` rather than
+/// reimplemented here, so callers no longer need their own copy of that
+/// computation to sign or track the transaction.
+///
+/// Free with `free_bytes_with_digest(ptr, bcs_len)` where `bcs_len` is the
+/// u32 read from the first 4 bytes.
+///
+/// Returns NULL on any build or serialisation error.
+///
+/// IMPORTANT: this call consumes (drops) the builder.
+/// Do NOT call `free_builder` afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn build_transaction_with_digest(
+    builder: *mut TransactionBuilder,
+) -> *mut u8 {
+    let builder = Box::from_raw(builder);
+    let tx = match builder.try_build() {
+        Ok(tx) => tx,
+        Err(e) => {
+            set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+    let digest: [u8; 32] = *tx.digest().inner();
+    let payload = match bcs::to_bytes(&tx) {
+        Ok(b) => b,
+        Err(e) => {
+            set_last_error(crate::error::Error::Input(e.to_string()));
+            return std::ptr::null_mut();
+        }
+    };
+    clear_last_error();
+
+    let total = 4 + payload.len() + digest.len();
+    let mut buf = Vec::<u8>::with_capacity(total);
+    buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&payload);
+    buf.extend_from_slice(&digest);
+    debug_assert_eq!(buf.len(), total);
+    debug_assert_eq!(buf.capacity(), total);
+    let ptr = buf.as_mut_ptr();
+    mem::forget(buf);
+    ptr
+}
+
+/// Free the buffer returned by `build_transaction_with_digest`.
+/// Pass the u32 BCS length read from the first 4 bytes as `bcs_len`.
+#[no_mangle]
+pub unsafe extern "C" fn free_bytes_with_digest(ptr: *mut u8, bcs_len: usize) {
+    let total = 4 + bcs_len + 32;
+    let _ = Vec::from_raw_parts(ptr, total, total);
+}
+
+// ── Online intent resolution ─────────────────────────────────────────────────
+
+/// Like `build_transaction`, but first resolves any outstanding intents
+/// (e.g. `CoinWithBalance`) against a live fullnode before serialising.
+///
+/// `build_transaction` fails with `Input("unable to resolve intents offline")`
+/// whenever an unresolved intent remains. This is the online counterpart: it
+/// opens a `sui_rpc::Client` against `rpc_url`, runs every registered
+/// `IntentResolver::resolve` to drain the intents map (selecting/merging
+/// coins to satisfy each `CoinWithBalance`), then serialises exactly like
+/// `build_transaction`.
+///
+/// Resolution is async and this FFI boundary is sync, so this call blocks on
+/// a small embedded current-thread Tokio runtime internally — do not call it
+/// from within an async context.
+///
+/// Only available when the `intents` feature is enabled.
+///
+/// `rpc_url_ptr` / `rpc_url_len` — UTF-8 fullnode RPC URL, e.g.
+/// `"https://fullnode.mainnet.sui.io:443"`.
+///
+/// Returns the same `[u32 payload_len][BCS bytes…]` buffer as
+/// `build_transaction`, freed the same way with `free_bytes`.
+///
+/// Returns NULL on any connection, resolution, build, or serialisation
+/// error — call `last_error_message` for details.
+///
+/// IMPORTANT: this call consumes (drops) the builder.
+/// Do NOT call `free_builder` afterwards.
+#[cfg(feature = "intents")]
+#[no_mangle]
+pub unsafe extern "C" fn build_transaction_resolved(
+    builder: *mut TransactionBuilder,
+    rpc_url_ptr: *const u8,
+    rpc_url_len: usize,
+) -> *mut u8 {
+    let mut builder = Box::from_raw(builder);
+    let bytes = slice::from_raw_parts(rpc_url_ptr, rpc_url_len);
+    let rpc_url = match std::str::from_utf8(bytes) {
+        Ok(s)  => s,
+        Err(e) => {
+            set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => {
+            set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let result = runtime.block_on(async {
+        let mut client = sui_rpc::Client::new(rpc_url)
+            .map_err(|e| crate::error::Error::Input(e.to_string()))?;
+        builder.resolve_intents(&mut client).await?;
+        let tx = builder.try_build()?;
+        bcs::to_bytes(&tx).map_err(|e| crate::error::Error::Input(e.to_string()))
+    });
+
+    let payload = match result {
+        Ok(b)  => b,
+        Err(e) => {
+            set_last_error(e);
+            return std::ptr::null_mut();
+        }
     };
+    clear_last_error();
+
     let total = 4 + payload.len();
     let mut buf = Vec::<u8>::with_capacity(total);
     buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());